@@ -0,0 +1,237 @@
+//! Built-in [`Generator`] that forwards missing items to a wrapped field.
+
+use syn::parse::ParseStream;
+use syn::spanned::Spanned;
+use syn::{Result, Token};
+
+use crate::util::{positional_arg_ident, respan_parsed, Args, Once, ParseArgs};
+use crate::{await_if_async, copy_gat_generics, Generator, ReceiverKind};
+
+/// Completes an impl by forwarding every missing item to the `#target` expression
+/// (e.g. `self.0`), optionally
+/// through a fully-qualified `<Ty as Trait>::` path.
+///
+/// This is the pattern used to make a trait usable through a newtype or decorator
+/// wrapper: declare the wrapper, then let `Delegate` fill in the boilerplate that just
+/// forwards to the wrapped value.
+///
+/// # Example
+/// ```ignore
+/// portrait_framework::proc_macro_filler!(delegate, portrait_framework::delegate::Delegate::new);
+///
+/// #[delegate(self.0)]
+/// impl MyTrait for Wrapper {}
+/// ```
+pub struct Delegate {
+    target:    syn::Expr,
+    target_ty: Option<syn::Type>,
+    trait_path: Option<syn::Path>,
+}
+
+impl Delegate {
+    /// Constructs a [`Delegate`] generator from filler macro arguments.
+    ///
+    /// # Panics
+    /// Panics (which the proc macro machinery reports as a compile error) if no target
+    /// expression was supplied, e.g. `delegate(self.inner)`.
+    pub fn new(Args(args): Args<DelegateArgs>) -> Self {
+        let target = args
+            .target
+            .try_get()
+            .unwrap_or_else(|| panic!("delegate() requires a target expression, e.g. delegate(self.inner)"));
+        Self { target, target_ty: args.target_ty.try_get(), trait_path: args.trait_path.try_get() }
+    }
+
+    /// Fully-qualified call syntax doesn't auto-ref like a method call does, so unlike
+    /// the dot-call path below, the target has to be explicitly borrowed to match
+    /// whatever `self` receiver the trait method declares.
+    fn qualified_call(
+        &self,
+        ident: &syn::Ident,
+        receiver: &ReceiverKind,
+        call_args: impl Iterator<Item = syn::Ident>,
+    ) -> proc_macro2::TokenStream {
+        let target = &self.target;
+        match &self.trait_path {
+            Some(trait_path) => {
+                let target = match receiver {
+                    ReceiverKind::Ref => quote::quote! { &#target },
+                    ReceiverKind::RefMut => quote::quote! { &mut #target },
+                    ReceiverKind::ByValue | ReceiverKind::None => quote::quote! { #target },
+                };
+                quote::quote! { <_ as #trait_path>::#ident(#target #(, #call_args)*) }
+            }
+            None => quote::quote! { #target.#ident(#(#call_args),*) },
+        }
+    }
+
+    /// Associated consts/types can't be reached through autoref like method calls can:
+    /// `<_ as Trait>::NAME` fails to infer (`E0283` for consts, `E0121` for types), so
+    /// unlike [`Self::qualified_call`] we need the wrapped field's actual type, not a
+    /// `_` placeholder. The macro arguments must therefore spell it out, e.g.
+    /// `delegate(self.inner: InnerType, as MyTrait)`.
+    fn qualified_path_or_err(&self, item: impl quote::ToTokens) -> Result<(&syn::Type, &syn::Path)> {
+        let target_ty = self.target_ty.as_ref();
+        let trait_path = self.trait_path.as_ref();
+        match (target_ty, trait_path) {
+            (Some(target_ty), Some(trait_path)) => Ok((target_ty, trait_path)),
+            _ => Err(syn::Error::new_spanned(
+                item,
+                "delegating an associated const or type requires both the wrapped field's \
+                 type and a qualified trait path, e.g. delegate(self.inner: InnerType, as MyTrait)",
+            )),
+        }
+    }
+}
+
+impl Generator for Delegate {
+    fn generate_const(&mut self, item: &syn::TraitItemConst) -> Result<syn::ImplItemConst> {
+        let ident = &item.ident;
+        let ty = &item.ty;
+        let (target_ty, trait_path) = self.qualified_path_or_err(item)?;
+
+        let generated: syn::ImplItemConst = syn::parse2(quote::quote! {
+            const #ident: #ty = <#target_ty as #trait_path>::#ident;
+        })?;
+        respan_parsed(&generated, item.span())
+    }
+
+    fn generate_method(&mut self, item: &syn::TraitItemMethod) -> Result<syn::ImplItemMethod> {
+        let mut sig = item.sig.clone();
+
+        let mut call_args = Vec::new();
+        for (index, input) in sig.inputs.iter_mut().enumerate() {
+            let syn::FnArg::Typed(pat_ty) = input else { continue };
+            let ident = positional_arg_ident(&pat_ty.pat, index);
+            call_args.push(ident.clone());
+            *pat_ty.pat = syn::Pat::Ident(syn::PatIdent {
+                attrs:     Vec::new(),
+                by_ref:    None,
+                mutability: None,
+                ident,
+                subpat:    None,
+            });
+        }
+
+        let receiver = ReceiverKind::of(&item.sig);
+        let call: syn::Expr = syn::parse2(self.qualified_call(&sig.ident, &receiver, call_args.into_iter()))?;
+        let call = await_if_async(call, item);
+
+        let generated: syn::ImplItemMethod = syn::parse2(quote::quote! {
+            #sig { #call }
+        })?;
+        respan_parsed(&generated, item.span())
+    }
+
+    fn generate_type(&mut self, item: &syn::TraitItemType) -> Result<syn::ImplItemType> {
+        let ident = &item.ident;
+        let (target_ty, trait_path) = self.qualified_path_or_err(item)?;
+
+        let mut impl_item: syn::ImplItemType = syn::parse2(quote::quote! {
+            type #ident = <#target_ty as #trait_path>::#ident;
+        })?;
+        copy_gat_generics(item, &mut impl_item);
+        respan_parsed(&impl_item, item.span())
+    }
+}
+
+/// Parsed arguments for [`Delegate::new`]: `delegate(self.inner: InnerType, as Trait)`.
+/// The `: InnerType` suffix is only required when delegating an associated const or
+/// type, which (unlike method calls) need the wrapped field's concrete type spelled
+/// out rather than inferred.
+#[derive(Default)]
+pub struct DelegateArgs {
+    target:     Once<syn::Expr>,
+    target_ty:  Once<syn::Type>,
+    trait_path: Once<syn::Path>,
+}
+
+impl ParseArgs for DelegateArgs {
+    fn parse_once(&mut self, input: ParseStream) -> Result<()> {
+        if input.peek(Token![as]) {
+            input.parse::<Token![as]>()?;
+            let path: syn::Path = input.parse()?;
+            let span = path.span();
+            self.trait_path.set(path, span)?;
+            return Ok(());
+        }
+
+        let expr: syn::Expr = input.parse()?;
+        // `self.inner: InnerType` parses as a single type ascription expression.
+        let (expr, ascribed_ty) = match expr {
+            syn::Expr::Type(syn::ExprType { expr, ty, .. }) => (*expr, Some(*ty)),
+            expr => (expr, None),
+        };
+        let span = expr.span();
+        self.target.set(expr, span)?;
+
+        if let Some(ty) = ascribed_ty {
+            let span = ty.span();
+            self.target_ty.set(ty, span)?;
+        } else if input.peek(Token![:]) {
+            input.parse::<Token![:]>()?;
+            let ty: syn::Type = input.parse()?;
+            let span = ty.span();
+            self.target_ty.set(ty, span)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::ToTokens;
+
+    use super::*;
+
+    fn delegate(trait_path: Option<&str>) -> Delegate {
+        Delegate {
+            target:    syn::parse_quote!(self.inner),
+            target_ty: Some(syn::parse_quote!(Inner)),
+            trait_path: trait_path.map(|path| syn::parse_str(path).unwrap()),
+        }
+    }
+
+    #[test]
+    fn dot_call_relies_on_autoref() {
+        let trait_item: syn::TraitItemMethod = syn::parse_quote!(fn foo(&mut self, x: u8) -> u8;);
+        let generated = delegate(None).generate_method(&trait_item).unwrap();
+        let tokens = generated.to_token_stream().to_string();
+        assert!(tokens.contains("self . inner . foo"));
+    }
+
+    #[test]
+    fn qualified_call_borrows_ref_receiver() {
+        let trait_item: syn::TraitItemMethod = syn::parse_quote!(fn foo(&self, x: u8) -> u8;);
+        let generated = delegate(Some("MyTrait")).generate_method(&trait_item).unwrap();
+        let tokens = generated.to_token_stream().to_string();
+        assert!(tokens.contains("& self . inner"));
+    }
+
+    #[test]
+    fn qualified_call_borrows_mut_receiver() {
+        let trait_item: syn::TraitItemMethod = syn::parse_quote!(fn foo(&mut self, x: u8););
+        let generated = delegate(Some("MyTrait")).generate_method(&trait_item).unwrap();
+        let tokens = generated.to_token_stream().to_string();
+        assert!(tokens.contains("& mut self . inner"));
+    }
+
+    #[test]
+    fn qualified_call_passes_by_value_receiver_unborrowed() {
+        let trait_item: syn::TraitItemMethod = syn::parse_quote!(fn foo(self) -> u8;);
+        let generated = delegate(Some("MyTrait")).generate_method(&trait_item).unwrap();
+        let tokens = generated.to_token_stream().to_string();
+        assert!(tokens.contains("foo (self . inner"));
+        assert!(!tokens.contains("& self . inner"));
+    }
+
+    #[test]
+    fn unqualified_const_or_type_requires_target_ty_and_trait_path() {
+        let mut without_trait_path = delegate(None);
+        let const_item: syn::TraitItemConst = syn::parse_quote!(const N: u8;);
+        assert!(without_trait_path.generate_const(&const_item).is_err());
+        let type_item: syn::TraitItemType = syn::parse_quote!(type T;);
+        assert!(without_trait_path.generate_type(&type_item).is_err());
+    }
+}
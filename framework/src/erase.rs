@@ -0,0 +1,263 @@
+//! Built-in [`Generator`] that erases associated types behind `Box<dyn Trait>`.
+
+use std::collections::{HashMap, HashSet};
+
+use quote::quote;
+use syn::parse::ParseStream;
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::visit::{self, Visit};
+use syn::visit_mut::{self, VisitMut};
+use syn::{Result, Token, TypeParamBound};
+
+use crate::util::{respan_parsed, Args, ParseArgs};
+use crate::Generator;
+
+/// Completes an impl by erasing the listed associated types to `Box<dyn Trait>`,
+/// borrowing the core trick `dynamize` uses to make a trait with associated types
+/// usable behind `dyn`.
+///
+/// Each listed associated type is filled in as `Box<dyn Bound>`, where `Bound` is the
+/// bound(s) the trait declares on that type. Any trait-provided default method whose
+/// signature mentions `Self::#Name` for an erased `#Name` is completed by rewriting
+/// those occurrences to the erased form and boxing the default body's return value, so
+/// the concrete impl satisfies the now-concrete signature.
+///
+/// # Example
+/// ```ignore
+/// portrait_framework::proc_macro_filler!(erase, portrait_framework::erase::Erase::new);
+///
+/// #[erase(Item)]
+/// impl Collection for MyVec {}
+/// ```
+pub struct Erase {
+    targets: HashSet<syn::Ident>,
+    bounds:  HashMap<syn::Ident, Punctuated<TypeParamBound, Token![+]>>,
+}
+
+impl Erase {
+    /// Constructs an [`Erase`] generator naming the associated types to erase.
+    pub fn new(Args(args): Args<EraseArgs>) -> Self { Self { targets: args.targets, bounds: HashMap::new() } }
+}
+
+impl Generator for Erase {
+    fn generate_const(&mut self, item: &syn::TraitItemConst) -> Result<syn::ImplItemConst> {
+        Err(syn::Error::new_spanned(
+            item,
+            format!("`erase` cannot complete associated constant `{}`; implement it manually", item.ident),
+        ))
+    }
+
+    fn generate_method(&mut self, item: &syn::TraitItemMethod) -> Result<syn::ImplItemMethod> {
+        // The boxed default body is spliced in through a synchronous IIFE below, which
+        // can't hold a `.await`; rather than emit something that fails to compile with a
+        // confusing closure-related error, refuse up front, the same way generate_const
+        // refuses consts it can't complete.
+        if item.sig.asyncness.is_some() {
+            return Err(syn::Error::new_spanned(
+                &item.sig,
+                format!(
+                    "`erase` cannot complete async method `fn {}`; implement it manually for the \
+                     erased associated type(s)",
+                    item.sig.ident,
+                ),
+            ));
+        }
+
+        // Only the *return type* needs its value boxed: rewriting `Self::#Name` in
+        // argument position doesn't change what the default body has to produce, since
+        // `Self::#Name` already resolves to the erased `Box<dyn Bound>` through the
+        // associated type we just generated.
+        let erased_return = self.erased_return_ident(&item.sig)?;
+
+        let mut sig = item.sig.clone();
+        for (ident, bound) in &self.bounds {
+            let mut visitor = EraseSelfAssoc { ident, bound };
+            visitor.visit_signature_mut(&mut sig);
+        }
+
+        let default_body = item.default.clone().ok_or_else(|| {
+            syn::Error::new_spanned(
+                &item.sig,
+                format!(
+                    "`erase` can only complete `fn {}` if the trait gives it a default body to \
+                     adapt; implement it manually for the erased associated type(s)",
+                    item.sig.ident,
+                ),
+            )
+        })?;
+
+        // The default body's tail expression becomes the boxed value; wrapping it in an
+        // immediately-invoked closure lets us box it without parsing the block apart.
+        let body = if erased_return.is_some() {
+            quote! { { Box::new((|| #default_body)()) } }
+        } else {
+            quote! { #default_body }
+        };
+
+        let generated: syn::ImplItemMethod = syn::parse2(quote! { #sig #body })?;
+        respan_parsed(&generated, item.span())
+    }
+
+    fn generate_type(&mut self, item: &syn::TraitItemType) -> Result<syn::ImplItemType> {
+        if !self.targets.contains(&item.ident) {
+            return Err(syn::Error::new_spanned(
+                item,
+                format!("associated type `{}` is not listed in erase(...) and has no other implementation", item.ident),
+            ));
+        }
+        if item.bounds.is_empty() {
+            return Err(syn::Error::new_spanned(
+                item,
+                format!("associated type `{}` has no bounds to erase into `Box<dyn _>`", item.ident),
+            ));
+        }
+
+        let bound = item.bounds.clone();
+        self.bounds.insert(item.ident.clone(), bound.clone());
+
+        let ident = &item.ident;
+        let generated: syn::ImplItemType = syn::parse2(quote! {
+            type #ident = Box<dyn #bound>;
+        })?;
+        respan_parsed(&generated, item.span())
+    }
+}
+
+impl Erase {
+    /// Returns the erased ident if `sig`'s return type is directly `Self::#Name` for
+    /// some erased `#Name`, `None` if the return type doesn't mention any erased type
+    /// at all, or an error if it mentions one nested inside another type (e.g.
+    /// `Option<Self::Item>`) — boxing only the direct case is correct; boxing a nested
+    /// occurrence would need to box the inner value, not the whole container.
+    fn erased_return_ident<'a>(&'a self, sig: &syn::Signature) -> Result<Option<&'a syn::Ident>> {
+        let ty = match &sig.output {
+            syn::ReturnType::Default => return Ok(None),
+            syn::ReturnType::Type(_, ty) => ty.as_ref(),
+        };
+
+        for ident in self.bounds.keys() {
+            if is_self_assoc(ty, ident) {
+                return Ok(Some(ident));
+            }
+        }
+
+        for ident in self.bounds.keys() {
+            let mut finder = FindSelfAssoc { ident, found: false };
+            finder.visit_type(ty);
+            if finder.found {
+                return Err(syn::Error::new_spanned(
+                    ty,
+                    format!(
+                        "`erase` only supports `Self::{ident}` as a method's direct return type, \
+                         not nested inside another type (e.g. `Option<Self::{ident}>`); implement \
+                         `fn {}` manually",
+                        sig.ident,
+                    ),
+                ));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+struct EraseSelfAssoc<'a> {
+    ident: &'a syn::Ident,
+    bound: &'a Punctuated<TypeParamBound, Token![+]>,
+}
+
+impl VisitMut for EraseSelfAssoc<'_> {
+    fn visit_type_mut(&mut self, ty: &mut syn::Type) {
+        if is_self_assoc(ty, self.ident) {
+            let bound = self.bound;
+            *ty = syn::parse_quote!(Box<dyn #bound>);
+            return;
+        }
+        visit_mut::visit_type_mut(self, ty);
+    }
+}
+
+struct FindSelfAssoc<'a> {
+    ident: &'a syn::Ident,
+    found: bool,
+}
+
+impl<'a> Visit<'a> for FindSelfAssoc<'_> {
+    fn visit_type(&mut self, ty: &'a syn::Type) {
+        if is_self_assoc(ty, self.ident) {
+            self.found = true;
+            return;
+        }
+        visit::visit_type(self, ty);
+    }
+}
+
+fn is_self_assoc(ty: &syn::Type, ident: &syn::Ident) -> bool {
+    let syn::Type::Path(type_path) = ty else { return false };
+    type_path.qself.is_none()
+        && type_path.path.segments.len() == 2
+        && type_path.path.segments[0].ident == "Self"
+        && type_path.path.segments[1].ident == *ident
+}
+
+/// Parsed arguments for [`Erase::new`]: `erase(Item, Output)`.
+#[derive(Default)]
+pub struct EraseArgs {
+    targets: HashSet<syn::Ident>,
+}
+
+impl ParseArgs for EraseArgs {
+    fn parse_once(&mut self, input: ParseStream) -> Result<()> {
+        let ident: syn::Ident = input.parse()?;
+        self.targets.insert(ident);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::ToTokens;
+
+    use super::*;
+
+    fn erase_with_item() -> Erase {
+        let mut erase = Erase { targets: HashSet::from([syn::parse_quote!(Item)]), bounds: HashMap::new() };
+        let type_item: syn::TraitItemType = syn::parse_quote!(type Item: std::fmt::Debug;);
+        erase.generate_type(&type_item).unwrap();
+        erase
+    }
+
+    #[test]
+    fn generate_type_boxes_the_trait_bound() {
+        let generated = erase_with_item().generate_type(&syn::parse_quote!(type Item: std::fmt::Debug;)).unwrap();
+        let tokens = generated.to_token_stream().to_string();
+        assert!(tokens.contains("Box < dyn std :: fmt :: Debug >"));
+    }
+
+    #[test]
+    fn generate_method_boxes_a_direct_erased_return() {
+        let method: syn::TraitItemMethod = syn::parse_quote! {
+            fn get(&self) -> Self::Item { self.value.clone() }
+        };
+        let generated = erase_with_item().generate_method(&method).unwrap();
+        let tokens = generated.to_token_stream().to_string();
+        assert!(tokens.contains("Box :: new"));
+    }
+
+    #[test]
+    fn generate_method_rejects_nested_erased_return() {
+        let method: syn::TraitItemMethod = syn::parse_quote! {
+            fn get(&self) -> Option<Self::Item> { None }
+        };
+        assert!(erase_with_item().generate_method(&method).is_err());
+    }
+
+    #[test]
+    fn generate_method_rejects_async() {
+        let method: syn::TraitItemMethod = syn::parse_quote! {
+            async fn get(&self) -> Self::Item { self.value.clone().await }
+        };
+        assert!(erase_with_item().generate_method(&method).is_err());
+    }
+}
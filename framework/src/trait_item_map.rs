@@ -2,13 +2,20 @@ extern crate proc_macro;
 
 use std::collections::HashMap;
 
-use proc_macro2::TokenStream;
-use quote::quote;
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, ToTokens};
 use syn::parse::Parse;
+use syn::spanned::Spanned;
 use syn::{Error, Result};
 
 use crate::{filler, Completer};
 
+pub mod delegate;
+pub mod erase;
+mod util;
+pub use delegate::Delegate;
+pub use erase::Erase;
+
 /// One-line wrapper that declares a filler macro.
 ///
 /// # Example
@@ -78,28 +85,241 @@ pub fn completer_filler2<ArgsT: Parse, GeneratorT: Generator>(
 pub fn complete(
     trait_items: &[syn::TraitItem],
     impl_block: &syn::ItemImpl,
+    generator: impl Generator,
+) -> syn::Result<syn::ItemImpl> {
+    complete_map(TraitItemMap::new(trait_items), impl_block, generator)
+}
+
+/// Like [`complete`], but takes an already-built [`TraitItemMap`] instead of a single
+/// trait's item slice, so a filler can complete an entire supertrait hierarchy (merged
+/// with [`merge_portraits`]) in one impl block.
+pub fn complete_map(
+    mut items: TraitItemMap,
+    impl_block: &syn::ItemImpl,
     mut generator: impl Generator,
 ) -> syn::Result<syn::ItemImpl> {
     let mut output = impl_block.clone();
 
-    let items = subtract_items(trait_items, impl_block)?;
+    items.minus(&ImplItemMap::new(impl_block))?;
     for trait_item in items.consts.values() {
         let impl_item = generator.generate_const(trait_item)?;
         output.items.push(syn::ImplItem::Const(impl_item));
     }
-    for trait_item in items.methods.values() {
-        let impl_item = generator.generate_method(trait_item)?;
-        output.items.push(syn::ImplItem::Method(impl_item));
-    }
+    // Types are completed before methods so a generator (e.g. `Erase`) can use an
+    // associated type it just filled in to complete a method's signature/body.
     for trait_item in items.types.values() {
         let impl_item = generator.generate_type(trait_item)?;
+        check_gat_generics(trait_item, &impl_item)?;
         output.items.push(syn::ImplItem::Type(impl_item));
     }
+    for trait_item in items.methods.values() {
+        let impl_item = generator.generate_method(trait_item)?;
+        output.items.push(syn::ImplItem::Method(impl_item));
+    }
 
     Ok(output)
 }
 
+/// Checks that a generated associated type repeats the trait's GAT generics and
+/// where-bounds, since [`Generator`] implementations are responsible for copying
+/// them over (see [`copy_gat_generics`]) and silently dropping them would make the
+/// impl item non-generic, a different (and usually non-compiling) item altogether.
+fn check_gat_generics(trait_item: &syn::TraitItemType, impl_item: &syn::ImplItemType) -> Result<()> {
+    let expected: Vec<_> = trait_item.generics.params.iter().collect();
+    let actual: Vec<_> = impl_item.generics.params.iter().collect();
+
+    let kinds_match = expected.len() == actual.len()
+        && expected.iter().zip(&actual).all(|(e, a)| generic_param_kind(e) == generic_param_kind(a));
+    if !kinds_match {
+        return Err(Error::new_spanned(
+            &impl_item.generics,
+            format!(
+                "`type {}` must repeat the trait's generic parameters `<{}>`",
+                trait_item.ident,
+                quote!(#(#expected),*),
+            ),
+        ));
+    }
+
+    let trait_predicates = trait_item.generics.where_clause.iter().flat_map(|wc| &wc.predicates);
+    for predicate in trait_predicates {
+        let predicate_ts = predicate.to_token_stream().to_string();
+        let satisfied = impl_item
+            .generics
+            .where_clause
+            .iter()
+            .flat_map(|wc| &wc.predicates)
+            .any(|p| p.to_token_stream().to_string() == predicate_ts);
+        if !satisfied {
+            return Err(Error::new_spanned(
+                &impl_item.ident,
+                format!("`type {}` is missing the trait's where-bound `{predicate_ts}`", trait_item.ident),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn generic_param_kind(param: &syn::GenericParam) -> u8 {
+    match param {
+        syn::GenericParam::Lifetime(_) => 0,
+        syn::GenericParam::Type(_) => 1,
+        syn::GenericParam::Const(_) => 2,
+    }
+}
+
+/// Checks that an impl's associated constant matches the trait's declared type.
+fn check_const_signature(trait_item: &syn::TraitItemConst, impl_item: &syn::ImplItemConst) -> Result<()> {
+    if trait_item.ty.to_token_stream().to_string() != impl_item.ty.to_token_stream().to_string() {
+        return Err(Error::new_spanned(
+            &impl_item.ty,
+            format!(
+                "`const {}` has type `{}`, but the trait declares `{}`",
+                impl_item.ident,
+                impl_item.ty.to_token_stream(),
+                trait_item.ty.to_token_stream(),
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Checks that an impl method's receiver, argument types and `async`-ness match the
+/// trait's declaration.
+fn check_method_signature(trait_item: &syn::TraitItemMethod, impl_item: &syn::ImplItemMethod) -> Result<()> {
+    let expected = &trait_item.sig;
+    let actual = &impl_item.sig;
+
+    let expected_receiver = ReceiverKind::of(expected);
+    let actual_receiver = ReceiverKind::of(actual);
+    if expected_receiver != actual_receiver {
+        return Err(Error::new_spanned(
+            &impl_item.sig,
+            format!(
+                "`fn {}` takes {}, but the trait declares {}",
+                actual.ident,
+                actual_receiver.describe(),
+                expected_receiver.describe(),
+            ),
+        ));
+    }
+
+    if expected.asyncness.is_some() != actual.asyncness.is_some() {
+        return Err(Error::new_spanned(
+            &impl_item.sig,
+            format!(
+                "`fn {}` must {}be declared `async`, to match the trait",
+                actual.ident,
+                if expected.asyncness.is_some() { "" } else { "not " },
+            ),
+        ));
+    }
+
+    let expected_inputs: Vec<_> = typed_inputs(expected).collect();
+    let actual_inputs: Vec<_> = typed_inputs(actual).collect();
+    let inputs_match = expected_inputs.len() == actual_inputs.len()
+        && expected_inputs
+            .iter()
+            .zip(&actual_inputs)
+            .all(|(e, a)| e.ty.to_token_stream().to_string() == a.ty.to_token_stream().to_string());
+    if !inputs_match {
+        return Err(Error::new_spanned(
+            &impl_item.sig,
+            format!(
+                "`fn {}` has argument types `({})`, but the trait declares `({})`",
+                actual.ident,
+                quote!(#(#actual_inputs),*),
+                quote!(#(#expected_inputs),*),
+            ),
+        ));
+    }
+
+    if expected.output.to_token_stream().to_string() != actual.output.to_token_stream().to_string() {
+        return Err(Error::new_spanned(
+            &actual.output,
+            format!(
+                "`fn {}` returns `{}`, but the trait declares `{}`",
+                actual.ident,
+                describe_return(&actual.output),
+                describe_return(&expected.output),
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+fn describe_return(output: &syn::ReturnType) -> String {
+    match output {
+        syn::ReturnType::Default => "()".to_owned(),
+        syn::ReturnType::Type(_, ty) => ty.to_token_stream().to_string(),
+    }
+}
+
+fn typed_inputs(sig: &syn::Signature) -> impl Iterator<Item = &syn::PatType> {
+    sig.inputs.iter().filter_map(|arg| match arg {
+        syn::FnArg::Typed(pat_ty) => Some(pat_ty),
+        syn::FnArg::Receiver(_) => None,
+    })
+}
+
+#[derive(PartialEq, Eq)]
+enum ReceiverKind {
+    None,
+    ByValue,
+    Ref,
+    RefMut,
+}
+
+impl ReceiverKind {
+    fn of(sig: &syn::Signature) -> Self {
+        match sig.inputs.first() {
+            Some(syn::FnArg::Receiver(receiver)) if receiver.reference.is_none() => Self::ByValue,
+            Some(syn::FnArg::Receiver(receiver)) if receiver.mutability.is_some() => Self::RefMut,
+            Some(syn::FnArg::Receiver(_)) => Self::Ref,
+            _ => Self::None,
+        }
+    }
+
+    fn describe(&self) -> &'static str {
+        match self {
+            Self::None => "no `self` receiver",
+            Self::ByValue => "`self`",
+            Self::Ref => "`&self`",
+            Self::RefMut => "`&mut self`",
+        }
+    }
+}
+
+/// Copies a trait associated type's generic parameters and where-clause onto a
+/// generated impl associated type, so GAT declarations survive into the impl.
+/// [`complete`] rejects an impl type whose generics don't match the trait's, so
+/// generators that complete a generic associated type should call this.
+pub fn copy_gat_generics(trait_item: &syn::TraitItemType, impl_item: &mut syn::ImplItemType) {
+    impl_item.generics = trait_item.generics.clone();
+}
+
+/// Wraps `call` in `.await` if `method` is declared `async fn` in the trait, so a
+/// delegating/defaulting generator can forward to an async trait method without
+/// having to special-case it.
+pub fn await_if_async(call: syn::Expr, method: &syn::TraitItemMethod) -> syn::Expr {
+    if method.sig.asyncness.is_some() {
+        syn::parse_quote!(#call.await)
+    } else {
+        call
+    }
+}
+
 /// Generates missing items.
+///
+/// Implementations get the full trait item for context, so they can see whether a
+/// method is `async` ([`syn::Signature::asyncness`]) or an associated type carries its
+/// own generics and where-clause ([`syn::TraitItemType::generics`]) — the cases that
+/// need extra care when completing a [`generate_method`](Generator::generate_method) or
+/// [`generate_type`](Generator::generate_type). A completed GAT must repeat the trait's
+/// generics ([`copy_gat_generics`] does this for you); [`complete`] rejects it otherwise.
 pub trait Generator {
     /// Implements an associated constant.
     fn generate_const(&mut self, item: &syn::TraitItemConst) -> Result<syn::ImplItemConst>;
@@ -121,6 +341,17 @@ pub fn subtract_items<'t>(
     Ok(items)
 }
 
+/// Combines several captured portraits (e.g. a trait and its supertraits) into one
+/// [`TraitItemMap`], so a single filler invocation can complete an entire supertrait
+/// hierarchy in one impl block with [`complete_map`].
+pub fn merge_portraits<'t>(portraits: &[&'t [syn::TraitItem]]) -> Result<TraitItemMap<'t>> {
+    let mut merged = TraitItemMap::default();
+    for portrait in portraits {
+        merged.merge(TraitItemMap::new(portrait))?;
+    }
+    Ok(merged)
+}
+
 /// Indexes items in a trait by namespaced identifier.
 #[derive(Default)]
 pub struct TraitItemMap<'t> {
@@ -154,36 +385,81 @@ impl<'t> TraitItemMap<'t> {
     }
 
     /// Removes the items found in the impl, leaving only unimplemented items.
+    ///
+    /// An impl item that matches a trait item by name but not by structure (wrong
+    /// receiver, argument types, `async`-ness, associated type generics, ...) is
+    /// rejected here with a diagnostic anchored at the impl item, rather than being
+    /// silently accepted and surfacing as a confusing error later.
     pub fn minus(&mut self, impl_items: &ImplItemMap) -> Result<()> {
         for (ident, impl_item) in &impl_items.consts {
-            if self.consts.remove(ident).is_none() {
-                return Err(Error::new_spanned(
-                    impl_item,
-                    "no associated constant called {ident} in trait",
-                ));
+            match self.consts.remove(ident) {
+                Some(trait_item) => check_const_signature(trait_item, impl_item)?,
+                None => {
+                    return Err(Error::new_spanned(
+                        impl_item,
+                        format!("no associated constant called `{ident}` in trait"),
+                    ))
+                }
             }
         }
 
         for (ident, impl_item) in &impl_items.methods {
-            if self.methods.remove(ident).is_none() {
-                return Err(Error::new_spanned(
-                    impl_item,
-                    "no associated function called {ident} in trait",
-                ));
+            match self.methods.remove(ident) {
+                Some(trait_item) => check_method_signature(trait_item, impl_item)?,
+                None => {
+                    return Err(Error::new_spanned(
+                        impl_item,
+                        format!("no associated function called `{ident}` in trait"),
+                    ))
+                }
             }
         }
 
         for (ident, impl_item) in &impl_items.types {
-            if self.types.remove(ident).is_none() {
-                return Err(Error::new_spanned(
-                    impl_item,
-                    "no associated type called {ident} in trait",
-                ));
+            match self.types.remove(ident) {
+                Some(trait_item) => check_gat_generics(trait_item, impl_item)?,
+                None => {
+                    return Err(Error::new_spanned(
+                        impl_item,
+                        format!("no associated type called `{ident}` in trait"),
+                    ))
+                }
             }
         }
 
         Ok(())
     }
+
+    /// Merges another trait's items into this map, for completing supertraits or
+    /// several unrelated portraits in one impl block (see [`merge_portraits`]). A name
+    /// declared in both maps is an error rather than a silent override, joining both
+    /// declarations' spans the same way a double-set filler argument is rejected.
+    pub fn merge(&mut self, other: Self) -> Result<()> {
+        merge_unique(&mut self.consts, other.consts, "associated constant")?;
+        merge_unique(&mut self.methods, other.methods, "associated function")?;
+        merge_unique(&mut self.types, other.types, "associated type")?;
+        Ok(())
+    }
+}
+
+fn merge_unique<V: Spanned + Copy>(
+    into: &mut HashMap<syn::Ident, V>,
+    from: HashMap<syn::Ident, V>,
+    kind: &str,
+) -> Result<()> {
+    // Sorted so that, when several names collide, which one is reported first is
+    // deterministic rather than depending on hashmap iteration order.
+    let mut from: Vec<_> = from.into_iter().collect();
+    from.sort_by(|(a, _), (b, _)| a.to_string().cmp(&b.to_string()));
+
+    for (ident, item) in from {
+        let new_span = item.span();
+        if let Some(existing) = into.insert(ident.clone(), item) {
+            let span = Span::join(&new_span, existing.span()).unwrap_or(new_span);
+            return Err(Error::new(span, format!("{kind} `{ident}` is declared in more than one merged portrait")));
+        }
+    }
+    Ok(())
 }
 
 /// Indexes items in an impl block by namespaced identifier.
@@ -0,0 +1,102 @@
+//! Parsing helpers shared by the built-in [`Generator`](crate::Generator) implementations.
+//!
+//! This mirrors the argument-parsing machinery in `portrait_codegen`'s internal `util`
+//! module; it lives here too because built-in generators such as [`Delegate`](crate::delegate::Delegate)
+//! are constructed from user code (via [`proc_macro_filler!`](crate::proc_macro_filler)),
+//! not from within the codegen crate itself.
+#![allow(dead_code)] // not every helper is used by every generator
+
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::ToTokens;
+use syn::parse::{Parse, ParseStream, Parser};
+use syn::Result;
+
+pub(crate) struct Once<T>(pub(crate) Option<(Span, T)>);
+
+impl<T> Default for Once<T> {
+    fn default() -> Self { Self(None) }
+}
+
+impl<T> Once<T> {
+    pub(crate) fn set(&mut self, value: T, span: Span) -> Result<()> {
+        if let Some((old_span, _)) = self.0.replace((span, value)) {
+            return Err(syn::Error::new(
+                Span::join(&span, old_span).unwrap_or(span),
+                "Argument cannot be set twice",
+            ));
+        }
+        Ok(())
+    }
+
+    pub(crate) fn try_get(self) -> Option<T> { self.0.map(|(_, t)| t) }
+
+    pub(crate) fn get_or(self, f: impl FnOnce() -> T) -> T { self.try_get().unwrap_or_else(f) }
+}
+
+pub(crate) trait ParseArgs: Default {
+    fn parse_once(&mut self, input: ParseStream) -> Result<()>;
+}
+
+pub(crate) struct Args<T>(pub(crate) T);
+impl<T: ParseArgs> Parse for Args<T> {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut args = T::default();
+
+        while !input.is_empty() {
+            args.parse_once(input)?;
+
+            if let Err(err) = input.parse::<syn::Token![,]>() {
+                if !input.is_empty() {
+                    return Err(err);
+                }
+            }
+        }
+
+        Ok(Self(args))
+    }
+}
+
+/// Returns `pat`'s ident if it is a plain ident, or otherwise a synthetic positional
+/// identifier `__arg{index}` with the span of the original pattern, so diagnostics still
+/// point at it. The caller replaces the pattern with the returned ident.
+pub(crate) fn positional_arg_ident(pat: &syn::Pat, index: usize) -> syn::Ident {
+    if let syn::Pat::Ident(pat_ident) = pat {
+        if pat_ident.by_ref.is_none() && pat_ident.subpat.is_none() {
+            return pat_ident.ident.clone();
+        }
+    }
+
+    syn::Ident::new(&format!("__arg{index}"), pat.span())
+}
+
+use syn::spanned::Spanned;
+
+pub(crate) fn copy_with_span<T: ToTokens, P: Parser<Output = T>>(
+    t: &T,
+    parser: P,
+    span: Span,
+) -> Result<T> {
+    let mut ts = t.to_token_stream();
+    ts = copy_ts_with_span(ts, span);
+    parser.parse2(ts)
+}
+
+/// Re-spans an already-parsed syntax tree so every token points at `span`, so a
+/// generated item reports errors at the original trait item instead of the filler's
+/// own call site.
+pub(crate) fn respan_parsed<T: ToTokens + Parse>(item: &T, span: Span) -> Result<T> {
+    copy_with_span(item, T::parse, span)
+}
+
+fn copy_ts_with_span(ts: TokenStream, span: Span) -> TokenStream {
+    ts.into_iter()
+        .map(|mut tt| {
+            if let TokenTree::Group(group) = tt {
+                let group_ts = copy_ts_with_span(group.stream(), span);
+                tt = TokenTree::Group(proc_macro2::Group::new(group.delimiter(), group_ts));
+            }
+            tt.set_span(span);
+            tt
+        })
+        .collect()
+}